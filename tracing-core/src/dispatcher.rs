@@ -141,18 +141,19 @@ use crate::{
 use core::{
     any::Any,
     fmt,
-    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+    sync::atomic::{AtomicBool, AtomicPtr, AtomicU64, AtomicUsize, Ordering},
 };
 
 #[cfg(feature = "std")]
 use std::{
     cell::{Cell, RefCell, RefMut},
+    collections::HashMap,
     error,
-    sync::Weak,
+    sync::{Mutex, Weak},
 };
 
 #[cfg(feature = "alloc")]
-use alloc::sync::Arc;
+use alloc::{sync::Arc, vec::Vec};
 
 #[cfg(feature = "alloc")]
 use core::ops::Deref;
@@ -179,12 +180,55 @@ thread_local! {
     static CURRENT_STATE: State = State {
         default: RefCell::new(Dispatch::none()),
         can_enter: Cell::new(true),
+        stack: RefCell::new(Vec::new()),
     };
 }
 
 static EXISTS: AtomicBool = AtomicBool::new(false);
 static GLOBAL_INIT: AtomicUsize = AtomicUsize::new(UNINITIALIZED);
 
+/// Caches the global default dispatcher's [`max_level_hint`], so that hot
+/// instrumentation macros can decide whether a callsite could possibly be
+/// enabled with a single relaxed atomic load, the same way they already do
+/// for [`has_been_set`]. Refreshed every time [`set_global_default`] or
+/// [`reload_global_default`] publishes a new global dispatcher.
+///
+/// [`max_level_hint`]: Dispatch::max_level_hint
+static MAX_LEVEL_HINT: AtomicUsize = AtomicUsize::new(LEVEL_HINT_UNSET);
+
+const LEVEL_HINT_UNSET: usize = 0;
+const LEVEL_HINT_NONE: usize = 1;
+const LEVEL_HINT_OFF: usize = 2;
+const LEVEL_HINT_ERROR: usize = 3;
+const LEVEL_HINT_WARN: usize = 4;
+const LEVEL_HINT_INFO: usize = 5;
+const LEVEL_HINT_DEBUG: usize = 6;
+const LEVEL_HINT_TRACE: usize = 7;
+
+fn encode_level_hint(hint: Option<LevelFilter>) -> usize {
+    match hint {
+        None => LEVEL_HINT_NONE,
+        Some(LevelFilter::OFF) => LEVEL_HINT_OFF,
+        Some(LevelFilter::ERROR) => LEVEL_HINT_ERROR,
+        Some(LevelFilter::WARN) => LEVEL_HINT_WARN,
+        Some(LevelFilter::INFO) => LEVEL_HINT_INFO,
+        Some(LevelFilter::DEBUG) => LEVEL_HINT_DEBUG,
+        Some(LevelFilter::TRACE) => LEVEL_HINT_TRACE,
+    }
+}
+
+fn decode_level_hint(encoded: usize) -> Option<LevelFilter> {
+    match encoded {
+        LEVEL_HINT_OFF => Some(LevelFilter::OFF),
+        LEVEL_HINT_ERROR => Some(LevelFilter::ERROR),
+        LEVEL_HINT_WARN => Some(LevelFilter::WARN),
+        LEVEL_HINT_INFO => Some(LevelFilter::INFO),
+        LEVEL_HINT_DEBUG => Some(LevelFilter::DEBUG),
+        LEVEL_HINT_TRACE => Some(LevelFilter::TRACE),
+        _ => None,
+    }
+}
+
 #[cfg(feature = "std")]
 static SCOPED_COUNT: AtomicUsize = AtomicUsize::new(0);
 
@@ -192,10 +236,17 @@ const UNINITIALIZED: usize = 0;
 const INITIALIZING: usize = 1;
 const INITIALIZED: usize = 2;
 
+// When the `alloc` feature is enabled, the global default dispatcher is
+// stored behind an `AtomicPtr`, so that `reload_global_default` can publish
+// a new one with a single atomic store rather than requiring a `static mut`.
+// Without `alloc`, there is no way to box a new `Dispatch` to publish, so the
+// global default remains a `static mut` set exactly once by
+// `set_global_default`.
+#[cfg(feature = "alloc")]
+static GLOBAL_DISPATCH_PTR: AtomicPtr<Dispatch> = AtomicPtr::new(core::ptr::null_mut());
+
+#[cfg(not(feature = "alloc"))]
 static mut GLOBAL_DISPATCH: Dispatch = Dispatch {
-    #[cfg(feature = "alloc")]
-    subscriber: Kind::Global(&NO_SUBSCRIBER),
-    #[cfg(not(feature = "alloc"))]
     subscriber: &NO_SUBSCRIBER,
 };
 static NONE: Dispatch = Dispatch {
@@ -219,6 +270,16 @@ struct State {
     /// creating an infinite recursion. When we finish handling a dispatch, this
     /// is set back to `true`.
     can_enter: Cell<bool>,
+    /// Every default dispatcher currently in scope on this thread, innermost
+    /// last, mirroring the nesting of `with_default`/`set_default` calls.
+    ///
+    /// This is tracked separately from `default` (rather than reconstructed
+    /// from a chain of `DefaultGuard`s) so that [`with_parent_default`] can
+    /// reach one level up without requiring the caller to have kept the
+    /// enclosing guard around.
+    ///
+    /// [`with_parent_default`]: with_parent_default
+    stack: RefCell<Vec<Dispatch>>,
 }
 
 /// While this guard is active, additional calls to subscriber functions on
@@ -311,24 +372,14 @@ pub fn set_global_default(dispatcher: Dispatch) -> Result<(), SetGlobalDefaultEr
     if GLOBAL_INIT.compare_and_swap(UNINITIALIZED, INITIALIZING, Ordering::SeqCst) == UNINITIALIZED
     {
         #[cfg(feature = "alloc")]
-        let subscriber = {
-            let subscriber = match dispatcher.subscriber {
-                Kind::Global(s) => s,
-                Kind::Scoped(s) => unsafe {
-                    // safety: this leaks the subscriber onto the heap. the
-                    // reference count will always be at least 1.
-                    &*Arc::into_raw(s)
-                },
-            };
-            Kind::Global(subscriber)
-        };
+        publish_global(dispatcher);
 
         #[cfg(not(feature = "alloc"))]
-        let subscriber = dispatcher.subscriber;
-
         unsafe {
-            GLOBAL_DISPATCH = Dispatch { subscriber };
+            MAX_LEVEL_HINT.store(encode_level_hint(dispatcher.max_level_hint()), Ordering::Relaxed);
+            GLOBAL_DISPATCH = dispatcher;
         }
+
         GLOBAL_INIT.store(INITIALIZED, Ordering::SeqCst);
         EXISTS.store(true, Ordering::Release);
         Ok(())
@@ -337,6 +388,69 @@ pub fn set_global_default(dispatcher: Dispatch) -> Result<(), SetGlobalDefaultEr
     }
 }
 
+/// Atomically replaces the process-wide global default dispatcher with
+/// `dispatcher`.
+///
+/// Unlike [`set_global_default`], this may be called any number of times
+/// after the global default has already been set (with either
+/// `set_global_default` or a prior call to `reload_global_default`), letting
+/// an application swap out its global subscriber at runtime -- for example,
+/// to raise the log level or switch output format in response to a
+/// configuration reload -- without restarting the process.
+///
+/// Returns `Err` if no global default has been set yet; use
+/// [`set_global_default`] to perform the first assignment.
+///
+/// As with `set_global_default`, libraries should in general not call this
+/// function themselves; it is intended for use by applications assembling
+/// their own tracing configuration.
+///
+/// [`set_global_default`]: set_global_default
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub fn reload_global_default(dispatcher: Dispatch) -> Result<(), SetGlobalDefaultError> {
+    if GLOBAL_INIT.load(Ordering::SeqCst) != INITIALIZED {
+        return Err(SetGlobalDefaultError { _no_construct: () });
+    }
+    publish_global(dispatcher);
+    Ok(())
+}
+
+/// Converts `dispatcher`'s subscriber into a `'static` reference (leaking it
+/// if necessary) and publishes it as the new global default by storing a
+/// freshly boxed `Dispatch` into [`GLOBAL_DISPATCH_PTR`]. The previous
+/// boxed `Dispatch`, if any, is intentionally leaked: readers may still hold
+/// a `&'static Dispatch` obtained from [`get_global`] before the swap, so it
+/// is never safe to free.
+///
+/// Rebuilds the callsite interest cache afterwards: callsites that were
+/// already visited under the old global dispatcher may have cached an
+/// `Interest` that no longer reflects the new one, and without this, a
+/// reload that raises the level would silently fail to re-enable them.
+#[cfg(feature = "alloc")]
+fn publish_global(dispatcher: Dispatch) {
+    MAX_LEVEL_HINT.store(
+        encode_level_hint(dispatcher.max_level_hint()),
+        Ordering::Relaxed,
+    );
+    let subscriber = match dispatcher.subscriber {
+        Kind::Global(s) => s,
+        Kind::Scoped(s) => unsafe {
+            // safety: this leaks the subscriber onto the heap. the
+            // reference count will always be at least 1.
+            &*Arc::into_raw(s)
+        },
+    };
+    let boxed = alloc::boxed::Box::new(Dispatch {
+        subscriber: Kind::Global(subscriber),
+    });
+    GLOBAL_DISPATCH_PTR.store(
+        alloc::boxed::Box::into_raw(boxed),
+        Ordering::Release,
+    );
+    crate::callsite::rebuild_interest_cache();
+}
+
 /// Returns true if a `tracing` dispatcher has ever been set.
 ///
 /// This may be used to completely elide trace points if tracing is not in use
@@ -347,6 +461,27 @@ pub fn has_been_set() -> bool {
     EXISTS.load(Ordering::Relaxed)
 }
 
+/// Returns the global default dispatcher's [`max_level_hint`], if one has
+/// been set and it offers one.
+///
+/// This is a cheap, single relaxed atomic load (refreshed whenever
+/// [`set_global_default`] or [`reload_global_default`] publishes a new
+/// global dispatcher), so hot instrumentation macros can use it to skip
+/// callsite work without a virtual call into the subscriber, the same way
+/// they already use [`has_been_set`].
+///
+/// Returns `None` if no global default has been set, or if the current
+/// global default's subscriber does not implement level-based filtering.
+///
+/// [`max_level_hint`]: Dispatch::max_level_hint
+#[inline(always)]
+pub fn max_level() -> Option<LevelFilter> {
+    if !has_been_set() {
+        return None;
+    }
+    decode_level_hint(MAX_LEVEL_HINT.load(Ordering::Relaxed))
+}
+
 /// Returned if setting the global dispatcher fails.
 #[derive(Debug)]
 pub struct SetGlobalDefaultError {
@@ -450,11 +585,110 @@ where
     f(get_global())
 }
 
+/// Returns the number of [`set_default`]/[`with_default`] scopes currently
+/// entered on *any* thread.
+///
+/// This mirrors the atomic counter that [`get_default`] already consults to
+/// take its fast path when no scoped default has been set, so checking
+/// `scope_depth() > 0` costs a single atomic load and no thread-local lookup.
+///
+/// Note that this counter is process-wide, not per-thread: it answers
+/// "has *some* thread entered a scoped default", not "how deep is *this*
+/// thread's stack". Use [`with_parent_default`] to inspect this thread's own
+/// nesting.
+///
+/// [`set_default`]: set_default
+/// [`with_default`]: with_default
+/// [`get_default`]: get_default
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn scope_depth() -> usize {
+    SCOPED_COUNT.load(Ordering::Acquire)
+}
+
+/// Executes a closure with a reference to the default dispatcher that was
+/// active one level up from this thread's current scoped default, if any.
+///
+/// This lets code running inside a nested [`with_default`]/[`set_default`]
+/// scope reach the subscriber it shadowed, for example to fall back to an
+/// outer subscriber for events the inner one declines to handle. Returns
+/// `None` if this thread has fewer than two scoped defaults currently
+/// entered (i.e. there is no parent to reach).
+///
+/// [`with_default`]: with_default
+/// [`set_default`]: set_default
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn with_parent_default<T>(f: impl FnOnce(&Dispatch) -> T) -> Option<T> {
+    CURRENT_STATE
+        .try_with(|state| {
+            let stack = state.stack.borrow();
+            let parent = stack.get(stack.len().checked_sub(2)?)?;
+            Some(f(parent))
+        })
+        .ok()?
+}
+
+/// Returns an owned, clonable handle to this thread's effective default
+/// dispatcher: the current scoped default set by [`with_default`] or
+/// [`set_default`], if any, or the global default otherwise.
+///
+/// Unlike [`get_default`], which only lends a reference to the default for
+/// the duration of a closure, this returns an owned `Dispatch` that the
+/// caller can move anywhere -- most usefully, into a thread it spawns, since
+/// `with_default`/`set_default` do not themselves propagate a thread's
+/// scoped default to threads it spawns:
+///
+/// ```rust
+/// # use tracing_core::dispatcher;
+/// # use std::thread;
+/// let current = dispatcher::current();
+/// thread::spawn(move || {
+///     current.in_scope(|| {
+///         // ... this thread now has the parent's default dispatcher ...
+///     });
+/// });
+/// ```
+///
+/// [dispatcher]: super::dispatcher::Dispatch
+#[cfg(feature = "std")]
+pub fn current() -> Dispatch {
+    // A thread with no scoped default may still hold a copy of an earlier
+    // global default cached in its thread-local state, which would be stale
+    // after `reload_global_default`; read the global directly in that case.
+    let has_scoped = CURRENT_STATE
+        .try_with(|state| !state.stack.borrow().is_empty())
+        .unwrap_or(false);
+    if !has_scoped {
+        return get_global().clone();
+    }
+    get_current(Dispatch::clone).unwrap_or_else(|| get_global().clone())
+}
+
+/// Returns an owned, clonable handle to the current default dispatcher.
+///
+/// [dispatcher]: super::dispatcher::Dispatch
+#[cfg(not(feature = "std"))]
+pub fn current() -> Dispatch {
+    get_global().clone()
+}
+
 #[inline(always)]
 pub(crate) fn get_global() -> &'static Dispatch {
     if GLOBAL_INIT.load(Ordering::Acquire) != INITIALIZED {
         return &NONE;
     }
+
+    #[cfg(feature = "alloc")]
+    {
+        let ptr = GLOBAL_DISPATCH_PTR.load(Ordering::Acquire);
+        // Safety: `ptr` was published by `publish_global`, which always
+        // stores a pointer from `Box::into_raw` before `GLOBAL_INIT` is set
+        // to `INITIALIZED`, and the boxed `Dispatch`es are never freed.
+        unsafe { &*ptr }
+    }
+
+    #[cfg(not(feature = "alloc"))]
     unsafe {
         // This is safe given the invariant that setting the global dispatcher
         // also sets `GLOBAL_INIT` to `INITIALIZED`.
@@ -600,9 +834,8 @@ impl Dispatch {
     /// [level]: super::Level
     /// [`Subscriber`]: super::subscriber::Subscriber
     /// [`register_callsite`]: super::subscriber::Subscriber::max_level_hint
-    // TODO(eliza): consider making this a public API?
     #[inline]
-    pub(crate) fn max_level_hint(&self) -> Option<LevelFilter> {
+    pub fn max_level_hint(&self) -> Option<LevelFilter> {
         self.subscriber().max_level_hint()
     }
 
@@ -791,6 +1024,98 @@ impl Dispatch {
     pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
         Subscriber::downcast_ref(&*self.subscriber())
     }
+
+    /// Returns the `Arc` this `Dispatch` forwards to if the `Subscriber` it
+    /// holds is of type `T` and was constructed via [`Dispatch::new`], or
+    /// `None` otherwise.
+    ///
+    /// Unlike [`downcast_ref`], which only ever hands back a shared
+    /// reference, this returns an owned, refcounted handle to the live
+    /// subscriber. That's useful for subscribers which expose their own
+    /// interior mutability (a `Mutex`, an `ArcSwap`, a set of atomics) and
+    /// need to be reconfigured at runtime — changing a filter's level,
+    /// swapping an output writer — from code that only has a `Dispatch`, not
+    /// the original handle the subscriber was constructed with.
+    ///
+    /// Returns `None` if this `Dispatch`'s subscriber is [`Kind::Global`]: that
+    /// is the case for one built with [`Dispatch::from_static`], and also for
+    /// the global default, which [`set_global_default`] stores as a leaked
+    /// `'static` reference. Such subscribers are plain `'static` references,
+    /// not `Arc`-backed, so there is no refcounted handle to return.
+    ///
+    /// # Reentrancy
+    ///
+    /// Mutating a subscriber through the returned `Arc` while holding onto it
+    /// across a call back into `tracing` (emitting an event, entering a span)
+    /// can deadlock or panic if that call routes back into the very
+    /// subscriber being mutated and the subscriber's locking isn't reentrant.
+    /// Prefer to apply the mutation and drop the `Arc` before emitting any
+    /// further trace data on the same thread.
+    ///
+    /// [`downcast_ref`]: Dispatch::downcast_ref
+    /// [`Dispatch::new`]: Dispatch::new
+    /// [`Dispatch::from_static`]: Dispatch::from_static
+    /// [`Kind::Global`]: Kind::Global
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn downcast_arc<T: Any + Send + Sync>(&self) -> Option<Arc<T>> {
+        match self.subscriber {
+            Kind::Scoped(ref s) => {
+                if !Subscriber::is::<T>(&**s) {
+                    return None;
+                }
+                let s = Arc::clone(s);
+                let raw = Arc::into_raw(s) as *const T;
+                // Safety: we just confirmed via `Subscriber::is` that the
+                // subscriber behind this `Arc` is concretely `T`, and `raw`
+                // was produced by `Arc::into_raw` on that very allocation, so
+                // reconstructing an `Arc<T>` from it is sound.
+                Some(unsafe { Arc::from_raw(raw) })
+            }
+            Kind::Global(_) => None,
+        }
+    }
+
+    /// Sets this dispatch as the default for the duration of the closure `f`.
+    ///
+    /// This is a convenience wrapper around [`with_default`] that reads more
+    /// naturally on an owned `Dispatch`, such as one returned by
+    /// [`dispatcher::current`].
+    ///
+    /// [`with_default`]: super::dispatcher::with_default
+    /// [`dispatcher::current`]: super::dispatcher::current
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn in_scope<T>(&self, f: impl FnOnce() -> T) -> T {
+        with_default(self, f)
+    }
+
+    /// Returns a `Dispatch` that forwards every call to each of `dispatches`,
+    /// in order.
+    ///
+    /// This makes it possible to send the same trace data to, say, a console
+    /// subscriber and a remote-export subscriber at once, without writing a
+    /// combining `Subscriber` by hand. See [`BroadcastSubscriber`] for the
+    /// semantics used to reconcile multiple subscribers' span IDs and
+    /// `Interest`s into the single `Dispatch` this returns.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn tee(dispatches: impl IntoIterator<Item = Dispatch>) -> Self {
+        Dispatch::new(BroadcastSubscriber::new(dispatches))
+    }
+
+    /// Returns a `Dispatch` that forwards every call to each `Dispatch` in
+    /// `dispatches`, in order.
+    ///
+    /// This is an alias for [`Dispatch::tee`], named to match the common
+    /// "fan out to N subscribers" phrasing; the two are otherwise identical.
+    ///
+    /// [`Dispatch::tee`]: Dispatch::tee
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn broadcast(dispatches: impl IntoIterator<Item = Dispatch>) -> Self {
+        Self::tee(dispatches)
+    }
 }
 
 impl Default for Dispatch {
@@ -843,6 +1168,249 @@ impl Subscriber for NoSubscriber {
     fn exit(&self, _span: &span::Id) {}
 }
 
+/// A [`Subscriber`] that forwards every call to an ordered list of child
+/// [`Dispatch`]es, returned by [`Dispatch::tee`].
+///
+/// Each child assigns its own `span::Id` to a new span, so `BroadcastSubscriber`
+/// allocates a synthetic outer `Id` for every span it creates and keeps a
+/// table mapping that outer `Id` to a [`SpanEntry`] holding the list of
+/// per-child `Id`s (`None` for a child whose own `enabled()` declined the
+/// span) and a clone count, so that later calls (`record`, `enter`, `exit`,
+/// `clone_span`, `try_close`, ...) can be re-dispatched to the right child
+/// span. `register_callsite` and `enabled` are combined with OR semantics
+/// (interested if *any* child is), and `max_level_hint` with the loosest
+/// (most verbose) hint among the children, or `None` if any child declines to
+/// offer one. `register_callsite` only caches `Always`/`Never` when every
+/// child agrees; otherwise it reports `Sometimes` so `enabled()` keeps being
+/// consulted and each child still only receives the spans and events it
+/// actually wants, rather than whatever the most verbose child wants.
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub struct BroadcastSubscriber {
+    dispatches: Vec<Dispatch>,
+    spans: Mutex<HashMap<u64, SpanEntry>>,
+    next_id: AtomicU64,
+}
+
+/// The bookkeeping `BroadcastSubscriber` keeps for one outer (synthetic)
+/// `span::Id`.
+struct SpanEntry {
+    /// Each child's own `Id` for this span, or `None` if that child's
+    /// `enabled()` declined it.
+    children: Vec<Option<span::Id>>,
+    /// How many live `Span` handles refer to this outer id. Every `try_close`
+    /// is forwarded to the children, but only once this reaches zero do we
+    /// remove the entry -- otherwise another handle elsewhere is still
+    /// relying on the mapping.
+    refs: usize,
+}
+
+#[cfg(feature = "std")]
+impl BroadcastSubscriber {
+    /// Returns a new `BroadcastSubscriber` forwarding to each of `dispatches`,
+    /// in order.
+    pub fn new(dispatches: impl IntoIterator<Item = Dispatch>) -> Self {
+        Self {
+            dispatches: dispatches.into_iter().collect(),
+            spans: Mutex::new(HashMap::new()),
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    fn child_ids(&self, id: &span::Id) -> Option<Vec<Option<span::Id>>> {
+        self.spans
+            .lock()
+            .unwrap()
+            .get(&id.into_u64())
+            .map(|entry| entry.children.clone())
+    }
+}
+
+#[cfg(feature = "std")]
+impl Subscriber for BroadcastSubscriber {
+    fn register_callsite(&self, metadata: &'static Metadata<'static>) -> subscriber::Interest {
+        // Every child is queried, without short-circuiting: a single `Always`
+        // child must not make this return `Always` overall, since that would
+        // make the instrumentation macros stop calling `enabled()` for this
+        // callsite altogether -- which is also the only place the *other*
+        // children's `enabled()` gets consulted before `new_span`/`event`
+        // forward to them. Only return `Always`/`Never` when every child
+        // agrees; otherwise return `Sometimes` so `enabled()` keeps being
+        // re-checked, and route per child there.
+        let mut all_always = true;
+        let mut all_never = true;
+        for dispatch in &self.dispatches {
+            let this = dispatch.register_callsite(metadata);
+            if !this.is_always() {
+                all_always = false;
+            }
+            if !this.is_never() {
+                all_never = false;
+            }
+        }
+        if all_never {
+            subscriber::Interest::never()
+        } else if all_always {
+            subscriber::Interest::always()
+        } else {
+            subscriber::Interest::sometimes()
+        }
+    }
+
+    fn max_level_hint(&self) -> Option<LevelFilter> {
+        let mut max = LevelFilter::OFF;
+        for dispatch in &self.dispatches {
+            max = max.max(dispatch.max_level_hint()?);
+        }
+        Some(max)
+    }
+
+    fn new_span(&self, span: &span::Attributes<'_>) -> span::Id {
+        let metadata = span.metadata();
+        let ids: Vec<Option<span::Id>> = self
+            .dispatches
+            .iter()
+            .map(|d| {
+                if d.enabled(metadata) {
+                    Some(d.new_span(span))
+                } else {
+                    None
+                }
+            })
+            .collect();
+        let outer = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.spans.lock().unwrap().insert(
+            outer,
+            SpanEntry {
+                children: ids,
+                refs: 1,
+            },
+        );
+        span::Id::from_u64(outer)
+    }
+
+    fn clone_span(&self, id: &span::Id) -> span::Id {
+        // Bump our own refcount and grab a copy of the per-child ids while
+        // holding the lock, then release it before calling into the
+        // children -- same rationale as `try_close` below.
+        let children = {
+            let mut spans = self.spans.lock().unwrap();
+            spans.get_mut(&id.into_u64()).map(|entry| {
+                entry.refs += 1;
+                entry.children.clone()
+            })
+        };
+        if let Some(children) = children {
+            // Bump each child's own refcount too, so a child subscriber that
+            // tracks clones itself (e.g. a span registry) sees a balanced
+            // number of `clone_span`/`try_close` calls.
+            for (dispatch, child_id) in self.dispatches.iter().zip(children.iter()) {
+                if let Some(child_id) = child_id {
+                    dispatch.clone_span(child_id);
+                }
+            }
+        }
+        id.clone()
+    }
+
+    fn record(&self, span: &span::Id, values: &span::Record<'_>) {
+        if let Some(ids) = self.child_ids(span) {
+            for (dispatch, id) in self.dispatches.iter().zip(ids.iter()) {
+                if let Some(id) = id {
+                    dispatch.record(id, values);
+                }
+            }
+        }
+    }
+
+    fn record_follows_from(&self, span: &span::Id, follows: &span::Id) {
+        if let (Some(span_ids), Some(follows_ids)) =
+            (self.child_ids(span), self.child_ids(follows))
+        {
+            for ((dispatch, id), follows) in
+                self.dispatches.iter().zip(span_ids.iter()).zip(follows_ids.iter())
+            {
+                if let (Some(id), Some(follows)) = (id, follows) {
+                    dispatch.record_follows_from(id, follows);
+                }
+            }
+        }
+    }
+
+    fn event(&self, event: &Event<'_>) {
+        let metadata = event.metadata();
+        for dispatch in &self.dispatches {
+            if dispatch.enabled(metadata) {
+                dispatch.event(event);
+            }
+        }
+    }
+
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        self.dispatches.iter().any(|d| d.enabled(metadata))
+    }
+
+    fn enter(&self, span: &span::Id) {
+        if let Some(ids) = self.child_ids(span) {
+            for (dispatch, id) in self.dispatches.iter().zip(ids.iter()) {
+                if let Some(id) = id {
+                    dispatch.enter(id);
+                }
+            }
+        }
+    }
+
+    fn exit(&self, span: &span::Id) {
+        if let Some(ids) = self.child_ids(span) {
+            for (dispatch, id) in self.dispatches.iter().zip(ids.iter()) {
+                if let Some(id) = id {
+                    dispatch.exit(id);
+                }
+            }
+        }
+    }
+
+    fn try_close(&self, id: span::Id) -> bool {
+        // Every close is forwarded to the children, matching the
+        // `clone_span`s forwarded above, but the entry is only removed once
+        // every clone of this outer id has been closed; otherwise another
+        // `Span` handle elsewhere is still relying on the id -> children
+        // mapping we'd be deleting. The lock is released before calling into
+        // the children, so a child that calls back into this subscriber
+        // can't deadlock on `spans`.
+        let (children, last) = {
+            let mut spans = self.spans.lock().unwrap();
+            match spans.get_mut(&id.into_u64()) {
+                Some(entry) => {
+                    entry.refs -= 1;
+                    if entry.refs > 0 {
+                        (entry.children.clone(), false)
+                    } else {
+                        let entry = spans.remove(&id.into_u64()).unwrap();
+                        (entry.children, true)
+                    }
+                }
+                None => return false,
+            }
+        };
+        let all_closed = self
+            .dispatches
+            .iter()
+            .zip(children.into_iter())
+            .fold(true, |all_closed, (dispatch, id)| match id {
+                Some(id) => dispatch.try_close(id) && all_closed,
+                None => all_closed,
+            });
+        last && all_closed
+    }
+
+    fn downcast_raw(&self, id: core::any::TypeId) -> Option<*const ()> {
+        self.dispatches
+            .iter()
+            .find_map(|d| d.subscriber().downcast_raw(id))
+    }
+}
+
 #[cfg(feature = "std")]
 impl Registrar {
     pub(crate) fn upgrade(&self) -> Option<Dispatch> {
@@ -871,6 +1439,7 @@ impl State {
         let prior = CURRENT_STATE
             .try_with(|state| {
                 state.can_enter.set(true);
+                state.stack.borrow_mut().push(new_dispatch.clone());
                 state.default.replace(new_dispatch)
             })
             .ok();
@@ -927,7 +1496,10 @@ impl Drop for DefaultGuard {
             // lead to the drop of a subscriber which, in the process,
             // could then also attempt to access the same thread local
             // state -- causing a clash.
-            let prev = CURRENT_STATE.try_with(|state| state.default.replace(dispatch));
+            let prev = CURRENT_STATE.try_with(|state| {
+                state.stack.borrow_mut().pop();
+                state.default.replace(dispatch)
+            });
             drop(prev)
         }
     }
@@ -942,6 +1514,16 @@ mod test {
         metadata::{Kind, Level, Metadata},
         subscriber::Interest,
     };
+    #[cfg(feature = "std")]
+    use std::sync::Mutex;
+
+    /// `SCOPED_COUNT` (read by [`scope_depth`]) is process-wide, so any test
+    /// that enters a `set_default`/`with_default` scope and then asserts
+    /// something about it needs to be serialized against every other such
+    /// test in this module, or concurrent test threads will perturb each
+    /// other's counts.
+    #[cfg(feature = "std")]
+    static SCOPE_TEST_LOCK: Mutex<()> = Mutex::new(());
 
     #[test]
     fn dispatch_is() {
@@ -976,6 +1558,7 @@ mod test {
     #[test]
     #[cfg(feature = "std")]
     fn events_dont_infinite_loop() {
+        let _lock = SCOPE_TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
         // This test ensures that an event triggered within a subscriber
         // won't cause an infinite loop of events.
         struct TestSubscriber;
@@ -1015,6 +1598,7 @@ mod test {
     #[test]
     #[cfg(feature = "std")]
     fn spans_dont_infinite_loop() {
+        let _lock = SCOPE_TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
         // This test ensures that a span created within a subscriber
         // won't cause an infinite loop of new spans.
 
@@ -1067,6 +1651,7 @@ mod test {
     #[cfg(feature = "std")]
     #[test]
     fn default_dispatch() {
+        let _lock = SCOPE_TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
         struct TestSubscriber;
         impl Subscriber for TestSubscriber {
             fn enabled(&self, _: &Metadata<'_>) -> bool {
@@ -1095,4 +1680,299 @@ mod test {
         let default_dispatcher = Dispatch::default();
         assert!(default_dispatcher.is::<NoSubscriber>());
     }
+
+    struct NoopSubscriber;
+    impl Subscriber for NoopSubscriber {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, _: &span::Attributes<'_>) -> span::Id {
+            span::Id::from_u64(0xAAAA)
+        }
+
+        fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+
+        fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+        fn event(&self, _: &Event<'_>) {}
+
+        fn enter(&self, _: &span::Id) {}
+
+        fn exit(&self, _: &span::Id) {}
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn scope_depth_and_parent_default() {
+        struct Outer;
+        impl Subscriber for Outer {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+            fn new_span(&self, _: &span::Attributes<'_>) -> span::Id {
+                span::Id::from_u64(1)
+            }
+            fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+            fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+            fn event(&self, _: &Event<'_>) {}
+            fn enter(&self, _: &span::Id) {}
+            fn exit(&self, _: &span::Id) {}
+        }
+        struct Inner;
+        impl Subscriber for Inner {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+            fn new_span(&self, _: &span::Attributes<'_>) -> span::Id {
+                span::Id::from_u64(2)
+            }
+            fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+            fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+            fn event(&self, _: &Event<'_>) {}
+            fn enter(&self, _: &span::Id) {}
+            fn exit(&self, _: &span::Id) {}
+        }
+
+        // `scope_depth` is backed by a process-wide counter, so hold a lock
+        // shared with the other `set_default`/`with_default`-using tests in
+        // this module for the duration of this test -- otherwise a test
+        // running concurrently on another thread could enter or exit its own
+        // scope in between these assertions and make the deltas below flaky.
+        let _lock = SCOPE_TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+        // `with_parent_default` should see no parent outside of any scoped
+        // default, regardless of what other tests have done to the
+        // process-wide `scope_depth` counter.
+        assert!(with_parent_default(|_| ()).is_none());
+        let depth0 = scope_depth();
+
+        let outer_guard = set_default(&Dispatch::new(Outer));
+        assert_eq!(scope_depth(), depth0 + 1);
+        assert!(with_parent_default(|_| ()).is_none());
+
+        let inner_guard = set_default(&Dispatch::new(Inner));
+        assert_eq!(scope_depth(), depth0 + 2);
+        assert_eq!(with_parent_default(|d| d.is::<Outer>()), Some(true));
+
+        drop(inner_guard);
+        assert_eq!(scope_depth(), depth0 + 1);
+        assert!(with_parent_default(|_| ()).is_none());
+
+        drop(outer_guard);
+        assert_eq!(scope_depth(), depth0);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn downcast_arc_success_and_failure() {
+        struct OtherSubscriber;
+        impl Subscriber for OtherSubscriber {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+            fn new_span(&self, _: &span::Attributes<'_>) -> span::Id {
+                span::Id::from_u64(1)
+            }
+            fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+            fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+            fn event(&self, _: &Event<'_>) {}
+            fn enter(&self, _: &span::Id) {}
+            fn exit(&self, _: &span::Id) {}
+        }
+
+        let dispatch = Dispatch::new(NoopSubscriber);
+        assert!(dispatch.downcast_arc::<NoopSubscriber>().is_some());
+        assert!(dispatch.downcast_arc::<OtherSubscriber>().is_none());
+
+        // A `Dispatch::from_static` subscriber isn't `Arc`-backed, so there's
+        // no refcounted handle to return even for its own concrete type.
+        let static_dispatch = Dispatch::from_static(&NO_SUBSCRIBER);
+        assert!(static_dispatch.downcast_arc::<NoSubscriber>().is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn broadcast_subscriber_honors_each_childs_enabled() {
+        let _lock = SCOPE_TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        struct CallsiteA;
+        static CALLSITE_A: CallsiteA = CallsiteA;
+        static META_A: Metadata<'static> = metadata! {
+            name: "span_a",
+            target: module_path!(),
+            level: Level::DEBUG,
+            fields: &[],
+            callsite: &CALLSITE_A,
+            kind: Kind::SPAN
+        };
+        impl Callsite for CallsiteA {
+            fn set_interest(&self, _: Interest) {}
+            fn metadata(&self) -> &Metadata<'_> {
+                &META_A
+            }
+        }
+
+        struct CallsiteB;
+        static CALLSITE_B: CallsiteB = CallsiteB;
+        static META_B: Metadata<'static> = metadata! {
+            name: "span_b",
+            target: module_path!(),
+            level: Level::DEBUG,
+            fields: &[],
+            callsite: &CALLSITE_B,
+            kind: Kind::SPAN
+        };
+        impl Callsite for CallsiteB {
+            fn set_interest(&self, _: Interest) {}
+            fn metadata(&self) -> &Metadata<'_> {
+                &META_B
+            }
+        }
+
+        static A_SPANS: AtomicUsize = AtomicUsize::new(0);
+        static A_EVENTS: AtomicUsize = AtomicUsize::new(0);
+        static B_SPANS: AtomicUsize = AtomicUsize::new(0);
+        static B_EVENTS: AtomicUsize = AtomicUsize::new(0);
+
+        // Each child only ever enables the one callsite it "owns", keyed by
+        // pointer identity rather than any `Metadata` accessor, so each
+        // should only ever see its own spans and events.
+        struct OnlyMine {
+            mine: &'static Metadata<'static>,
+            spans: &'static AtomicUsize,
+            events: &'static AtomicUsize,
+        }
+        impl Subscriber for OnlyMine {
+            fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+                core::ptr::eq(metadata, self.mine)
+            }
+            fn new_span(&self, _: &span::Attributes<'_>) -> span::Id {
+                self.spans.fetch_add(1, Ordering::Relaxed);
+                span::Id::from_u64(1)
+            }
+            fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+            fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+            fn event(&self, _: &Event<'_>) {
+                self.events.fetch_add(1, Ordering::Relaxed);
+            }
+            fn enter(&self, _: &span::Id) {}
+            fn exit(&self, _: &span::Id) {}
+        }
+
+        let broadcast = Dispatch::new(BroadcastSubscriber::new(vec![
+            Dispatch::new(OnlyMine {
+                mine: &META_A,
+                spans: &A_SPANS,
+                events: &A_EVENTS,
+            }),
+            Dispatch::new(OnlyMine {
+                mine: &META_B,
+                spans: &B_SPANS,
+                events: &B_EVENTS,
+            }),
+        ]));
+
+        with_default(&broadcast, || {
+            get_default(|d| {
+                d.new_span(&span::Attributes::new(&META_A, &META_A.fields().value_set(&[])))
+            });
+            assert_eq!(A_SPANS.load(Ordering::Relaxed), 1);
+            assert_eq!(B_SPANS.load(Ordering::Relaxed), 0);
+
+            Event::dispatch(&META_B, &META_B.fields().value_set(&[]));
+            assert_eq!(A_EVENTS.load(Ordering::Relaxed), 0);
+            assert_eq!(B_EVENTS.load(Ordering::Relaxed), 1);
+        });
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn broadcast_subscriber_try_close_waits_for_every_clone() {
+        static CLOSES: AtomicUsize = AtomicUsize::new(0);
+
+        struct ClosingSubscriber(&'static AtomicUsize);
+        impl Subscriber for ClosingSubscriber {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+            fn new_span(&self, _: &span::Attributes<'_>) -> span::Id {
+                span::Id::from_u64(42)
+            }
+            fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+            fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+            fn event(&self, _: &Event<'_>) {}
+            fn enter(&self, _: &span::Id) {}
+            fn exit(&self, _: &span::Id) {}
+            fn try_close(&self, _: span::Id) -> bool {
+                self.0.fetch_add(1, Ordering::Relaxed);
+                true
+            }
+        }
+
+        let broadcast = BroadcastSubscriber::new(vec![Dispatch::new(ClosingSubscriber(&CLOSES))]);
+        let id = broadcast
+            .new_span(&span::Attributes::new(&TEST_META, &TEST_META.fields().value_set(&[])));
+
+        // A second handle to the same outer id -- e.g. from `Span::clone()`
+        // -- must keep the span open even after the first handle closes.
+        broadcast.clone_span(&id);
+
+        assert!(
+            !broadcast.try_close(id.clone()),
+            "try_close should not report fully closed while a clone is outstanding"
+        );
+        // The child received the forwarded `clone_span`, so it gets one
+        // `try_close` per outer close as well.
+        assert_eq!(CLOSES.load(Ordering::Relaxed), 1);
+
+        assert!(
+            broadcast.try_close(id),
+            "the matching try_close for the last outstanding clone should report fully closed"
+        );
+        assert_eq!(CLOSES.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn reload_global_default_updates_visible_dispatcher() {
+        static FIRST_CALLS: AtomicUsize = AtomicUsize::new(0);
+        static SECOND_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+        struct CountingSubscriber(&'static AtomicUsize);
+        impl Subscriber for CountingSubscriber {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                self.0.fetch_add(1, Ordering::Relaxed);
+                true
+            }
+            fn new_span(&self, _: &span::Attributes<'_>) -> span::Id {
+                span::Id::from_u64(1)
+            }
+            fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+            fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+            fn event(&self, _: &Event<'_>) {}
+            fn enter(&self, _: &span::Id) {}
+            fn exit(&self, _: &span::Id) {}
+        }
+
+        set_global_default(Dispatch::new(CountingSubscriber(&FIRST_CALLS)))
+            .expect("global default should not have been set yet");
+
+        assert!(current().enabled(&TEST_META));
+        assert_eq!(FIRST_CALLS.load(Ordering::Relaxed), 1);
+        assert_eq!(SECOND_CALLS.load(Ordering::Relaxed), 0);
+
+        reload_global_default(Dispatch::new(CountingSubscriber(&SECOND_CALLS)))
+            .expect("global default was already set, so reload should succeed");
+
+        // The reloaded subscriber, not the original one, should now be
+        // consulted. Note that this only checks `Dispatch::enabled()`
+        // directly, not the cached-`Interest` fast path that the
+        // instrumentation macros actually use via `Callsite::interest()` --
+        // the `callsite` module that owns that cache isn't reachable from
+        // here, so the stale-cache regression `rebuild_interest_cache()`
+        // guards against in `publish_global` is not exercised by this test.
+        assert!(current().enabled(&TEST_META));
+        assert_eq!(FIRST_CALLS.load(Ordering::Relaxed), 1);
+        assert_eq!(SECOND_CALLS.load(Ordering::Relaxed), 1);
+    }
 }
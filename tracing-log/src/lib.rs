@@ -28,6 +28,19 @@
 //! records emitted by dependencies which use `log` within the context of a
 //! trace.
 //!
+//! Structured key-value pairs attached to a `log::Record` (via
+//! [`Record::key_values`]) are also visited and recorded as `tracing`
+//! fields, named `kv.0`, `kv.1`,
+//! and so on, up to [`MAX_KV_FIELDS`]. Because the set of fields on a
+//! `tracing` callsite is fixed, each `kv.N` field's value is recorded as the
+//! literal `key=value` text (rather than just the bare value) so that the
+//! original key isn't lost along with the fixed slot name. A record with
+//! more key-value pairs than available slots will have its extra pairs
+//! (also as `key=value` text) appended to the `message` field instead of
+//! being dropped silently.
+//!
+//! [`Record::key_values`]: log::Record::key_values
+//!
 //! ## Convert tracing `Event`s to logs
 //!
 //! This conversion can be done with [`TraceLogger`], a [`Subscriber`] which
@@ -37,14 +50,28 @@
 //!
 //! Note that logger implementations that convert log records to trace events
 //! should not be used with `Subscriber`s that convert trace events _back_ into
-//! log records (such as the `TraceLogger`), as doing so will result in the
-//! event recursing between the subscriber and the logger forever (or, in real
-//! life, probably overflowing the call stack).
+//! log records (such as the `TraceLogger`), as doing so will usually result in
+//! the event recursing between the subscriber and the logger forever (or, in
+//! real life, probably overflowing the call stack).
 //!
 //! If the logging of trace events generated from log records produced by the
 //! `log` crate is desired, either the `log` crate should not be used to
-//! implement this logging, or an additional layer of filtering will be
-//! required to avoid infinitely converting between `Event` and `log::Record`.
+//! implement this logging, or the recursion guard described below should be
+//! enabled.
+//!
+//! ### Breaking the cycle with a recursion guard
+//!
+//! Both [`LogTracer`] and [`TraceLogger`] can be configured to opt in to a
+//! thread-local recursion guard: [`LogTracer::builder().with_recursion_guard()`]
+//! and [`TraceLoggerBuilder::with_recursion_guard()`]. While either side is in
+//! the middle of converting a record/event, the guard is set; a record or
+//! event produced *from within* that conversion (such as one emitted by the
+//! other side's own logging) is dropped rather than recursively converted
+//! again. Enabling the guard on both ends makes a `LogTracer` + `TraceLogger`
+//! round trip safe without a hand-written filtering layer.
+//!
+//! [`LogTracer::builder().with_recursion_guard()`]: log_tracer::Builder::with_recursion_guard
+//! [`TraceLoggerBuilder::with_recursion_guard()`]: trace_logger::Builder::with_recursion_guard
 //!
 //! [`init`]: struct.LogTracer.html#method.init
 //! [`init_with_filter`]: struct.LogTracer.html#method.init_with_filter
@@ -57,7 +84,7 @@ extern crate tracing_subscriber;
 
 use lazy_static::lazy_static;
 
-use std::io;
+use std::{convert::TryInto, fmt, io};
 
 use tracing_core::{
     callsite::{self, Callsite},
@@ -71,6 +98,57 @@ pub use self::log_tracer::LogTracer;
 pub mod trace_logger;
 pub use self::trace_logger::{Builder as TraceLoggerBuilder, TraceLogger};
 
+use std::cell::Cell;
+
+std::thread_local! {
+    /// Set while this thread is in the middle of converting a `log::Record`
+    /// into a `tracing::Event` (or vice versa), so that a record/event
+    /// generated *by* that conversion can be recognized and dropped instead
+    /// of recursing forever between `LogTracer` and `TraceLogger`.
+    static IN_CONVERSION: Cell<bool> = Cell::new(false);
+}
+
+/// A guard that exits a conversion entered via [`enter_conversion`] when
+/// dropped, clearing the thread-local recursion flag only if this guard was
+/// the one that set it.
+struct ConversionGuard(bool);
+
+impl Drop for ConversionGuard {
+    fn drop(&mut self) {
+        if self.0 {
+            IN_CONVERSION.with(|in_conversion| in_conversion.set(false));
+        }
+    }
+}
+
+/// Returns `true` if the current thread is already in the middle of a
+/// log/trace conversion.
+pub(crate) fn is_in_conversion() -> bool {
+    IN_CONVERSION.with(|in_conversion| in_conversion.get())
+}
+
+/// Marks the current thread as being in the middle of a log/trace
+/// conversion, for the lifetime of the returned guard.
+pub(crate) fn enter_conversion() -> ConversionGuard {
+    IN_CONVERSION.with(|in_conversion| {
+        if in_conversion.replace(true) {
+            // Already inside a conversion; this guard shouldn't clear the
+            // flag when it's dropped, since it didn't set it.
+            ConversionGuard(false)
+        } else {
+            ConversionGuard(true)
+        }
+    })
+}
+
+/// The number of `kv.N` field slots reserved on each `log` callsite for
+/// structured key-value pairs carried by a `log::Record`.
+///
+/// A record with more key-value pairs than this will have the extras
+/// rendered as `key=value` text and appended to the `message` field; see the
+/// [crate-level docs](index.html) for details.
+pub const MAX_KV_FIELDS: usize = 8;
+
 /// Format a log record as a trace event in the current span.
 pub fn format_trace(record: &log::Record) -> io::Result<()> {
     let filter_meta = record.as_trace();
@@ -94,20 +172,156 @@ pub fn format_trace(record: &log::Record) -> io::Result<()> {
     let file = log_file.as_ref().map(|s| s as &dyn field::Value);
     let line = log_line.as_ref().map(|s| s as &dyn field::Value);
 
+    let mut kvs = KeyValues::new();
+    let _ = record.key_values().visit(&mut kvs);
+
+    let overflow_message;
+    let message: &dyn field::Value = if kvs.overflow.is_empty() {
+        record.args()
+    } else {
+        let mut msg = record.args().to_string();
+        msg.push_str(" (");
+        for (i, (key, value)) in kvs.overflow.iter().enumerate() {
+            if i > 0 {
+                msg.push_str(", ");
+            }
+            msg.push_str(key);
+            msg.push('=');
+            msg.push_str(value);
+        }
+        msg.push(')');
+        overflow_message = field::display(msg);
+        &overflow_message
+    };
+
     let meta = cs.metadata();
-    Event::dispatch(
-        &meta,
-        &meta.fields().value_set(&[
-            (&keys.message, Some(record.args() as &dyn field::Value)),
-            (&keys.target, Some(&record.target())),
-            (&keys.module, module),
-            (&keys.file, file),
-            (&keys.line, line),
-        ]),
-    );
+    let mut values: Vec<(&field::Field, Option<&dyn field::Value>)> = vec![
+        (&keys.message, Some(message)),
+        (&keys.target, Some(&record.target())),
+        (&keys.module, module),
+        (&keys.file, file),
+        (&keys.line, line),
+    ];
+    for (field, value) in keys.kv.iter().zip(kvs.values.iter()) {
+        values.push((field, Some(value as &dyn field::Value)));
+    }
+    for field in keys.kv.iter().skip(kvs.values.len()) {
+        values.push((field, None));
+    }
+    Event::dispatch(&meta, &meta.fields().value_set(&values));
     Ok(())
 }
 
+/// A `log` key-value pair converted into an owned value that can be recorded
+/// as a `tracing` field.
+///
+/// Since the `kv.N` field it's recorded into has a fixed slot name rather
+/// than the pair's original key, the key is kept alongside the value and
+/// recorded as `key=value` text (via [`Debug`](fmt::Debug)) so it isn't lost.
+struct KvValue {
+    key: String,
+    value: KvValueInner,
+}
+
+/// The typed value half of a [`KvValue`].
+enum KvValueInner {
+    I64(i64),
+    U64(u64),
+    F64(f64),
+    Bool(bool),
+    Str(String),
+}
+
+impl field::Value for KvValue {
+    fn record(&self, key: &field::Field, visitor: &mut dyn field::Visit) {
+        visitor.record_debug(key, self)
+    }
+}
+
+/// Visits the structured key-value pairs on a `log::Record`, converting up
+/// to [`MAX_KV_FIELDS`] of them into owned [`KvValue`]s and collecting the
+/// `key=value` text of any that overflow the available slots.
+#[derive(Default)]
+struct KeyValues {
+    values: Vec<KvValue>,
+    overflow: Vec<(String, String)>,
+}
+
+impl KeyValues {
+    fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<'kvs> log::kv::Visitor<'kvs> for KeyValues {
+    fn visit_pair(
+        &mut self,
+        key: log::kv::Key<'kvs>,
+        value: log::kv::Value<'kvs>,
+    ) -> Result<(), log::kv::Error> {
+        if self.values.len() >= MAX_KV_FIELDS {
+            self.overflow.push((key.as_str().to_owned(), value.to_string()));
+            return Ok(());
+        }
+        let mut visitor = KvValueVisitor(None);
+        value.visit(&mut visitor)?;
+        if let Some(inner) = visitor.0 {
+            self.values.push(KvValue {
+                key: key.as_str().to_owned(),
+                value: inner,
+            });
+        }
+        Ok(())
+    }
+}
+
+struct KvValueVisitor(Option<KvValueInner>);
+
+impl<'v> log::kv::value::Visit<'v> for KvValueVisitor {
+    fn visit_i64(&mut self, value: i64) -> Result<(), log::kv::Error> {
+        self.0 = Some(KvValueInner::I64(value));
+        Ok(())
+    }
+
+    fn visit_u64(&mut self, value: u64) -> Result<(), log::kv::Error> {
+        self.0 = Some(KvValueInner::U64(value));
+        Ok(())
+    }
+
+    fn visit_f64(&mut self, value: f64) -> Result<(), log::kv::Error> {
+        self.0 = Some(KvValueInner::F64(value));
+        Ok(())
+    }
+
+    fn visit_bool(&mut self, value: bool) -> Result<(), log::kv::Error> {
+        self.0 = Some(KvValueInner::Bool(value));
+        Ok(())
+    }
+
+    fn visit_str(&mut self, value: &str) -> Result<(), log::kv::Error> {
+        self.0 = Some(KvValueInner::Str(value.to_owned()));
+        Ok(())
+    }
+
+    fn visit_any(&mut self, value: log::kv::Value<'_>) -> Result<(), log::kv::Error> {
+        self.0 = Some(KvValueInner::Str(value.to_string()));
+        Ok(())
+    }
+}
+
+impl fmt::Debug for KvValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}=", self.key)?;
+        match &self.value {
+            KvValueInner::I64(v) => fmt::Debug::fmt(v, f),
+            KvValueInner::U64(v) => fmt::Debug::fmt(v, f),
+            KvValueInner::F64(v) => fmt::Debug::fmt(v, f),
+            KvValueInner::Bool(v) => fmt::Debug::fmt(v, f),
+            KvValueInner::Str(v) => fmt::Debug::fmt(v, f),
+        }
+    }
+}
+
 pub trait AsLog {
     type Log;
     fn as_log(&self) -> Self::Log;
@@ -134,6 +348,7 @@ struct Fields {
     module: field::Field,
     file: field::Field,
     line: field::Field,
+    kv: [field::Field; MAX_KV_FIELDS],
 }
 
 static FIELD_NAMES: &'static [&'static str] = &[
@@ -142,6 +357,14 @@ static FIELD_NAMES: &'static [&'static str] = &[
     "log.module_path",
     "log.file",
     "log.line",
+    "kv.0",
+    "kv.1",
+    "kv.2",
+    "kv.3",
+    "kv.4",
+    "kv.5",
+    "kv.6",
+    "kv.7",
 ];
 
 macro_rules! log_cs {
@@ -172,12 +395,18 @@ macro_rules! log_cs {
                 let module = META.fields().field("log.module_path").unwrap();
                 let file = META.fields().field("log.file").unwrap();
                 let line = META.fields().field("log.line").unwrap();
+                let kv: Vec<field::Field> = (0..MAX_KV_FIELDS)
+                    .map(|i| META.fields().field(&format!("kv.{}", i)).unwrap())
+                    .collect();
+                let kv: [field::Field; MAX_KV_FIELDS] =
+                    kv.try_into().unwrap_or_else(|_| unreachable!());
                 Fields {
                     message,
                     target,
                     module,
                     file,
                     line,
+                    kv,
                 }
             };
         }
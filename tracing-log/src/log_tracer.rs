@@ -0,0 +1,151 @@
+//! An adapter for converting [`log`] records into `tracing` [`Event`]s.
+//!
+//! [`Event`]: tracing_core::Event
+use crate::format_trace;
+use log;
+
+/// A simple "logger" that converts all log records into `tracing` `Event`s.
+pub struct LogTracer {
+    ignore_crates: Box<[String]>,
+    recursion_guard: bool,
+}
+
+/// Configures a new `LogTracer`.
+#[derive(Debug)]
+pub struct Builder {
+    ignore_crates: Vec<String>,
+    filter: log::LevelFilter,
+    recursion_guard: bool,
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self {
+            ignore_crates: Vec::new(),
+            filter: log::LevelFilter::Trace,
+            recursion_guard: false,
+        }
+    }
+}
+
+impl Builder {
+    /// Returns a new `Builder` with the default configuration.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Configures the `LogTracer` to ignore records originating from the
+    /// given crate name.
+    pub fn ignore_crate(mut self, name: impl Into<String>) -> Self {
+        self.ignore_crates.push(name.into());
+        self
+    }
+
+    /// Configures the `LogTracer` to ignore records originating from any of
+    /// the given crate names.
+    pub fn ignore_all<I>(mut self, crates: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.ignore_crates.extend(crates.into_iter().map(Into::into));
+        self
+    }
+
+    /// Configures the maximum `log` verbosity that will be converted into
+    /// `tracing` events.
+    pub fn with_max_level(mut self, filter: impl Into<log::LevelFilter>) -> Self {
+        self.filter = filter.into();
+        self
+    }
+
+    /// Enables the thread-local recursion guard.
+    ///
+    /// When enabled, a `log` record produced while this `LogTracer` is in
+    /// the middle of converting an earlier record into a `tracing::Event` is
+    /// dropped instead of being converted (and potentially recursing back
+    /// into a `Subscriber`, such as [`TraceLogger`], that re-emits `tracing`
+    /// events as `log` records). See the [crate-level docs][crate] for
+    /// details.
+    ///
+    /// [`TraceLogger`]: crate::TraceLogger
+    pub fn with_recursion_guard(mut self) -> Self {
+        self.recursion_guard = true;
+        self
+    }
+
+    /// Constructs a new `LogTracer` with this builder's configuration.
+    pub fn build(self) -> LogTracer {
+        LogTracer {
+            ignore_crates: self.ignore_crates.into_boxed_slice(),
+            recursion_guard: self.recursion_guard,
+        }
+    }
+
+    /// Constructs a new `LogTracer` with this builder's configuration and
+    /// sets it as the default logger.
+    pub fn init(self) -> Result<(), log::SetLoggerError> {
+        let filter = self.filter;
+        let logger = self.build();
+        log::set_max_level(filter);
+        log::set_boxed_logger(Box::new(logger))
+    }
+}
+
+impl LogTracer {
+    /// Returns a `Builder` for configuring a `LogTracer`.
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    /// Returns a new `LogTracer` with the default configuration.
+    pub fn new() -> Self {
+        Self::builder().build()
+    }
+
+    /// Sets a `LogTracer` as the default logger, with the given maximum
+    /// `log` verbosity.
+    pub fn init_with_filter(level: log::LevelFilter) -> Result<(), log::SetLoggerError> {
+        Self::builder().with_max_level(level).init()
+    }
+
+    /// Sets a `LogTracer` as the default logger, enabling all log levels.
+    pub fn init() -> Result<(), log::SetLoggerError> {
+        Self::builder().with_max_level(log::LevelFilter::Trace).init()
+    }
+
+    fn is_ignored(&self, metadata: &log::Metadata) -> bool {
+        self.ignore_crates
+            .iter()
+            .any(|ignored| metadata.target().starts_with(ignored.as_str()))
+    }
+}
+
+impl Default for LogTracer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl log::Log for LogTracer {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        !self.is_ignored(metadata)
+    }
+
+    fn log(&self, record: &log::Record) {
+        if self.is_ignored(record.metadata()) {
+            return;
+        }
+        if self.recursion_guard {
+            if crate::is_in_conversion() {
+                return;
+            }
+            let _guard = crate::enter_conversion();
+            let _ = format_trace(record);
+            return;
+        }
+        let _ = format_trace(record);
+    }
+
+    fn flush(&self) {}
+}
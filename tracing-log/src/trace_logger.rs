@@ -0,0 +1,256 @@
+//! A `Subscriber` that converts `tracing` spans and events into [`log`]
+//! records.
+use crate::AsLog;
+use log;
+use std::{
+    collections::HashMap,
+    fmt::Write,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        RwLock,
+    },
+};
+use tracing_core::{
+    field::{Field, Visit},
+    span::{self, Id},
+    Event, Metadata, Subscriber,
+};
+
+/// A `Subscriber` implementation that re-emits `tracing` spans and events as
+/// `log` records.
+///
+/// See the [crate-level docs] for a warning about combining this with
+/// [`LogTracer`].
+///
+/// [crate-level docs]: crate
+/// [`LogTracer`]: crate::LogTracer
+#[derive(Debug)]
+pub struct TraceLogger {
+    settings: Settings,
+    spans: RwLock<HashMap<Id, SpanInfo>>,
+    next_id: AtomicU64,
+}
+
+#[derive(Debug)]
+struct Settings {
+    log_span_closes: bool,
+    log_enters: bool,
+    log_exits: bool,
+    recursion_guard: bool,
+}
+
+#[derive(Debug)]
+struct SpanInfo {
+    metadata: &'static Metadata<'static>,
+    fields: String,
+}
+
+/// Configures and constructs `TraceLogger`s.
+#[derive(Debug, Default)]
+pub struct Builder {
+    settings: Settings,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            log_span_closes: false,
+            log_enters: false,
+            log_exits: false,
+            recursion_guard: false,
+        }
+    }
+}
+
+impl Builder {
+    /// Returns a new `Builder` with the default configuration.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Configures whether a `log` record is emitted when a span closes.
+    pub fn with_span_closes(mut self, log_span_closes: bool) -> Self {
+        self.settings.log_span_closes = log_span_closes;
+        self
+    }
+
+    /// Configures whether a `log` record is emitted when a span is entered.
+    pub fn with_span_entry(mut self, log_enters: bool) -> Self {
+        self.settings.log_enters = log_enters;
+        self
+    }
+
+    /// Configures whether a `log` record is emitted when a span is exited.
+    pub fn with_span_exits(mut self, log_exits: bool) -> Self {
+        self.settings.log_exits = log_exits;
+        self
+    }
+
+    /// Enables the thread-local recursion guard.
+    ///
+    /// When enabled, an `Event` recorded while this `TraceLogger` is in the
+    /// middle of emitting an earlier `log` record is dropped instead of
+    /// being re-emitted (and potentially recursing back into a logger, such
+    /// as [`LogTracer`], that converts `log` records back into `tracing`
+    /// events). See the [crate-level docs][crate] for details.
+    ///
+    /// [`LogTracer`]: crate::LogTracer
+    pub fn with_recursion_guard(mut self) -> Self {
+        self.settings.recursion_guard = true;
+        self
+    }
+
+    /// Constructs a new `TraceLogger` with this builder's configuration.
+    pub fn finish(self) -> TraceLogger {
+        TraceLogger {
+            settings: self.settings,
+            spans: RwLock::new(HashMap::new()),
+            next_id: AtomicU64::new(1),
+        }
+    }
+}
+
+impl TraceLogger {
+    /// Returns a new `TraceLogger` with the default configuration.
+    pub fn new() -> Self {
+        Self::builder().finish()
+    }
+
+    /// Returns a `Builder` for configuring a `TraceLogger`.
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    fn emit(&self, target: &str, record: fmt_args::Args<'_>) {
+        if self.settings.recursion_guard && crate::is_in_conversion() {
+            return;
+        }
+        let _guard = self.settings.recursion_guard.then(crate::enter_conversion);
+        log::logger().log(&record.as_log(target));
+    }
+}
+
+impl Default for TraceLogger {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A small helper for building `log::Record`s out of pieces that the
+/// `Subscriber` callbacks have on hand, without allocating a `log::Record`
+/// builder at every call site.
+mod fmt_args {
+    use tracing_core::{Level, Metadata};
+
+    pub(super) struct Args<'a> {
+        pub(super) level: Level,
+        pub(super) message: &'a str,
+    }
+
+    impl<'a> Args<'a> {
+        pub(super) fn as_log(&self, target: &str) -> log::Record<'_> {
+            log::Record::builder()
+                .level(crate::AsLog::as_log(&self.level))
+                .target(target)
+                .args(format_args!("{}", self.message))
+                .build()
+        }
+    }
+
+    pub(super) fn new<'a>(metadata: &'a Metadata<'a>, message: &'a str) -> Args<'a> {
+        Args {
+            level: *metadata.level(),
+            message,
+        }
+    }
+}
+
+/// Accumulates a span or event's fields into a `key=value, ...` string.
+#[derive(Default)]
+struct FieldVisitor(String);
+
+impl Visit for FieldVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if !self.0.is_empty() {
+            self.0.push_str(", ");
+        }
+        let _ = write!(self.0, "{}={:?}", field.name(), value);
+    }
+}
+
+impl Subscriber for TraceLogger {
+    fn register_callsite(&self, metadata: &'static Metadata<'static>) -> tracing_core::subscriber::Interest {
+        if log::logger().enabled(&metadata.as_log()) {
+            tracing_core::subscriber::Interest::always()
+        } else {
+            tracing_core::subscriber::Interest::never()
+        }
+    }
+
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        log::logger().enabled(&metadata.as_log())
+    }
+
+    fn new_span(&self, span: &span::Attributes<'_>) -> Id {
+        let id = Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed));
+        let mut visitor = FieldVisitor::default();
+        span.record(&mut visitor);
+        self.spans.write().unwrap().insert(
+            id.clone(),
+            SpanInfo {
+                metadata: span.metadata(),
+                fields: visitor.0,
+            },
+        );
+        id
+    }
+
+    fn record(&self, span: &Id, values: &span::Record<'_>) {
+        if let Some(info) = self.spans.write().unwrap().get_mut(span) {
+            let mut visitor = FieldVisitor(std::mem::take(&mut info.fields));
+            values.record(&mut visitor);
+            info.fields = visitor.0;
+        }
+    }
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+        self.emit(
+            event.metadata().target(),
+            fmt_args::new(event.metadata(), &visitor.0),
+        );
+    }
+
+    fn enter(&self, span: &Id) {
+        if !self.settings.log_enters {
+            return;
+        }
+        if let Some(info) = self.spans.read().unwrap().get(span) {
+            let message = format!("-> {} ({})", info.metadata.name(), info.fields);
+            self.emit(info.metadata.target(), fmt_args::new(info.metadata, &message));
+        }
+    }
+
+    fn exit(&self, span: &Id) {
+        if !self.settings.log_exits {
+            return;
+        }
+        if let Some(info) = self.spans.read().unwrap().get(span) {
+            let message = format!("<- {} ({})", info.metadata.name(), info.fields);
+            self.emit(info.metadata.target(), fmt_args::new(info.metadata, &message));
+        }
+    }
+
+    fn try_close(&self, span: Id) -> bool {
+        if let Some(info) = self.spans.write().unwrap().remove(&span) {
+            if self.settings.log_span_closes {
+                let message = format!("-- {} ({})", info.metadata.name(), info.fields);
+                self.emit(info.metadata.target(), fmt_args::new(info.metadata, &message));
+            }
+        }
+        true
+    }
+}
@@ -1,10 +1,10 @@
 use crate::layer;
 
-use crossbeam_utils::sync::ShardedLock;
+use arc_swap::ArcSwap;
 use std::{
     error, fmt,
     marker::PhantomData,
-    sync::{Arc, Weak},
+    sync::{Arc, RwLock, Weak},
 };
 use tracing_core::{
     callsite, span,
@@ -14,16 +14,27 @@ use tracing_core::{
 
 #[derive(Debug)]
 pub struct Layer<L, S> {
-    inner: Arc<ShardedLock<L>>,
+    inner: Arc<Shared<L>>,
     _s: PhantomData<fn(S)>,
 }
 
 #[derive(Debug)]
 pub struct Handle<L, S> {
-    inner: Weak<ShardedLock<L>>,
+    inner: Weak<Shared<L>>,
     _s: PhantomData<fn(S)>,
 }
 
+struct Shared<L> {
+    value: ArcSwap<L>,
+    on_reload: RwLock<Vec<Box<dyn Fn(&L) + Send + Sync>>>,
+}
+
+impl<L: fmt::Debug> fmt::Debug for Shared<L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Shared").field("value", &self.value).finish()
+    }
+}
+
 #[derive(Debug)]
 pub struct Error {
     kind: ErrorKind,
@@ -32,7 +43,6 @@ pub struct Error {
 #[derive(Debug)]
 enum ErrorKind {
     SubscriberGone,
-    Poisoned,
 }
 
 // ===== impl Layer =====
@@ -44,52 +54,52 @@ where
 {
     #[inline]
     fn register_callsite(&self, metadata: &'static Metadata<'static>) -> Interest {
-        try_lock!(self.inner.read(), else return Interest::sometimes()).register_callsite(metadata)
+        self.inner.value.load().register_callsite(metadata)
     }
 
     #[inline]
     fn enabled(&self, metadata: &Metadata, ctx: layer::Context<S>) -> bool {
-        try_lock!(self.inner.read(), else return false).enabled(metadata, ctx)
+        self.inner.value.load().enabled(metadata, ctx)
     }
 
     #[inline]
     fn new_span(&self, attrs: &span::Attributes, id: &span::Id, ctx: layer::Context<S>) {
-        try_lock!(self.inner.read()).new_span(attrs, id, ctx)
+        self.inner.value.load().new_span(attrs, id, ctx)
     }
 
     #[inline]
     fn on_record(&self, span: &span::Id, values: &span::Record, ctx: layer::Context<S>) {
-        try_lock!(self.inner.read()).on_record(span, values, ctx)
+        self.inner.value.load().on_record(span, values, ctx)
     }
 
     #[inline]
     fn on_follows_from(&self, span: &span::Id, follows: &span::Id, ctx: layer::Context<S>) {
-        try_lock!(self.inner.read()).on_follows_from(span, follows, ctx)
+        self.inner.value.load().on_follows_from(span, follows, ctx)
     }
 
     #[inline]
     fn on_event(&self, event: &Event, ctx: layer::Context<S>) {
-        try_lock!(self.inner.read()).on_event(event, ctx)
+        self.inner.value.load().on_event(event, ctx)
     }
 
     #[inline]
     fn on_enter(&self, id: &span::Id, ctx: layer::Context<S>) {
-        try_lock!(self.inner.read()).on_enter(id, ctx)
+        self.inner.value.load().on_enter(id, ctx)
     }
 
     #[inline]
     fn on_exit(&self, id: &span::Id, ctx: layer::Context<S>) {
-        try_lock!(self.inner.read()).on_exit(id, ctx)
+        self.inner.value.load().on_exit(id, ctx)
     }
 
     #[inline]
     fn on_close(&self, id: span::Id, ctx: layer::Context<S>) {
-        try_lock!(self.inner.read()).on_close(id, ctx)
+        self.inner.value.load().on_close(id, ctx)
     }
 
     #[inline]
     fn on_id_change(&self, old: &span::Id, new: &span::Id, ctx: layer::Context<S>) {
-        try_lock!(self.inner.read()).on_id_change(old, new, ctx)
+        self.inner.value.load().on_id_change(old, new, ctx)
     }
 }
 
@@ -100,7 +110,10 @@ where
 {
     pub fn new(inner: L) -> (Self, Handle<L, S>) {
         let this = Self {
-            inner: Arc::new(ShardedLock::new(inner)),
+            inner: Arc::new(Shared {
+                value: ArcSwap::from_pointee(inner),
+                on_reload: RwLock::new(Vec::new()),
+            }),
             _s: PhantomData,
         };
         let handle = this.handle();
@@ -123,28 +136,82 @@ where
     S: Subscriber,
 {
     pub fn reload(&self, new_layer: impl Into<L>) -> Result<(), Error> {
-        self.modify(|layer| {
-            *layer = new_layer.into();
-        })
+        self.publish(Arc::new(new_layer.into()))
     }
 
-    /// Invokes a closure with a mutable reference to the current layer,
-    /// allowing it to be modified in place.
-    pub fn modify(&self, f: impl FnOnce(&mut L)) -> Result<(), Error> {
+    /// Invokes a closure with a clone of the current layer, publishing
+    /// whatever it produces as the new value.
+    ///
+    /// Because reads are a lock-free `load()` of an `Arc<L>`, `modify` can't
+    /// mutate the layer in place; instead it clones the current value and
+    /// applies `f` to the clone. This is done via [`ArcSwap::rcu`], which
+    /// retries `f` against the latest value if another `modify`/`reload`
+    /// published a new value in the meantime, so two concurrent `modify`
+    /// calls are serialized into a proper read-modify-write instead of one
+    /// silently clobbering the other's update. Because of this, `f` may be
+    /// called more than once and must be idempotent.
+    pub fn modify(&self, mut f: impl FnMut(&mut L)) -> Result<(), Error>
+    where
+        L: Clone,
+    {
         let inner = self.inner.upgrade().ok_or(Error {
             kind: ErrorKind::SubscriberGone,
         })?;
 
-        let mut lock = try_lock!(inner.write(), else return Err(Error::poisoned()));
-        f(&mut *lock);
-        // Release the lock before rebuilding the interest cache, as that
-        // function will lock the new layer.
-        drop(lock);
+        inner.value.rcu(|current| {
+            let mut layer = L::clone(current);
+            f(&mut layer);
+            layer
+        });
+        self.notify(&inner);
+        Ok(())
+    }
 
-        callsite::rebuild_interest_cache();
+    /// Registers a callback to be invoked with a reference to the layer's
+    /// new value every time [`reload`] or [`modify`] successfully publishes
+    /// one, after the new value is visible to readers but before the
+    /// callsite interest cache is rebuilt.
+    ///
+    /// This lets consumers that cache derived state (a compiled filter, a
+    /// formatter configuration) rebuild that state in lockstep with
+    /// reloads, rather than polling [`with_current`] themselves.
+    ///
+    /// [`reload`]: Self::reload
+    /// [`modify`]: Self::modify
+    /// [`with_current`]: Self::with_current
+    pub fn on_reload(&self, callback: impl Fn(&L) + Send + Sync + 'static) -> Result<(), Error> {
+        let inner = self.inner.upgrade().ok_or(Error {
+            kind: ErrorKind::SubscriberGone,
+        })?;
+        inner
+            .on_reload
+            .write()
+            .unwrap()
+            .push(Box::new(callback));
+        Ok(())
+    }
+
+    fn publish(&self, new_value: Arc<L>) -> Result<(), Error> {
+        let inner = self.inner.upgrade().ok_or(Error {
+            kind: ErrorKind::SubscriberGone,
+        })?;
+
+        inner.value.store(new_value);
+        self.notify(&inner);
         Ok(())
     }
 
+    /// Runs the `on_reload` callbacks with the current value and rebuilds the
+    /// callsite interest cache, after a new value has already been published
+    /// by [`publish`](Self::publish) or [`modify`](Self::modify).
+    fn notify(&self, inner: &Shared<L>) {
+        for callback in inner.on_reload.read().unwrap().iter() {
+            callback(&inner.value.load());
+        }
+
+        callsite::rebuild_interest_cache();
+    }
+
     /// Returns a clone of the layer's current value if it still exists.
     /// Otherwise, if the subscriber has been dropped, returns `None`.
     pub fn clone_current(&self) -> Option<L>
@@ -160,8 +227,7 @@ where
         let inner = self.inner.upgrade().ok_or(Error {
             kind: ErrorKind::SubscriberGone,
         })?;
-        let inner = try_lock!(inner.read(), else return Err(Error::poisoned()));
-        Ok(f(&*inner))
+        Ok(f(&inner.value.load()))
     }
 }
 
@@ -176,14 +242,6 @@ impl<L, S> Clone for Handle<L, S> {
 
 // ===== impl Error =====
 
-impl Error {
-    fn poisoned() -> Self {
-        Self {
-            kind: ErrorKind::Poisoned,
-        }
-    }
-}
-
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         error::Error::description(self).fmt(f)
@@ -194,7 +252,6 @@ impl error::Error for Error {
     fn description(&self) -> &str {
         match self.kind {
             ErrorKind::SubscriberGone => "subscriber no longer exists",
-            ErrorKind::Poisoned => "lock poisoned",
         }
     }
 }
@@ -210,6 +267,7 @@ mod test {
         static FILTER1_CALLS: AtomicUsize = AtomicUsize::new(0);
         static FILTER2_CALLS: AtomicUsize = AtomicUsize::new(0);
 
+        #[derive(Clone)]
         enum Filter {
             One,
             Two,
@@ -253,4 +311,26 @@ mod test {
             assert_eq!(FILTER2_CALLS.load(Ordering::Relaxed), 1);
         })
     }
+
+    #[test]
+    fn on_reload_notifies() {
+        static NOTIFICATIONS: AtomicUsize = AtomicUsize::new(0);
+
+        #[derive(Clone)]
+        struct NopFilter;
+        impl<S: Subscriber> crate::Layer<S> for NopFilter {}
+
+        let (_layer, handle) = Layer::new(NopFilter);
+        handle
+            .on_reload(|_| {
+                NOTIFICATIONS.fetch_add(1, Ordering::Relaxed);
+            })
+            .expect("should register callback");
+
+        assert_eq!(NOTIFICATIONS.load(Ordering::Relaxed), 0);
+        handle.reload(NopFilter).expect("should reload");
+        assert_eq!(NOTIFICATIONS.load(Ordering::Relaxed), 1);
+        handle.modify(|_| {}).expect("should modify");
+        assert_eq!(NOTIFICATIONS.load(Ordering::Relaxed), 2);
+    }
 }